@@ -0,0 +1,50 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use stark101rs::field::FieldElement;
+use stark101rs::utils::poly_mul_coeffs;
+use std::hint::black_box;
+
+fn field_benches(c: &mut Criterion) {
+    let a = FieldElement::random_nonzero_seeded(1);
+    let b = FieldElement::random_nonzero_seeded(2);
+
+    c.bench_function("field mul", |bench| {
+        bench.iter(|| black_box(a) * black_box(b))
+    });
+    c.bench_function("field inverse", |bench| {
+        bench.iter(|| black_box(a).inverse())
+    });
+    c.bench_function("field inverse_fast", |bench| {
+        bench.iter(|| black_box(a).inverse_fast())
+    });
+
+    // up to the STARK-101 evaluation domain size
+    for log_n in [10, 13] {
+        let elements: Vec<_> = (0..1u64 << log_n)
+            .map(FieldElement::random_nonzero_seeded)
+            .collect();
+        c.bench_function(&format!("field batch_inverse 2^{log_n}"), |bench| {
+            bench.iter(|| FieldElement::batch_inverse(black_box(&elements)))
+        });
+    }
+}
+
+fn poly_mul_benches(c: &mut Criterion) {
+    let mut group = c.benchmark_group("poly_mul_coeffs");
+    // schoolbook multiply is quadratic, keep the sample count low
+    group.sample_size(10);
+    for log_n in [10, 13] {
+        let a: Vec<_> = (0..1u64 << log_n)
+            .map(FieldElement::random_nonzero_seeded)
+            .collect();
+        let b: Vec<_> = (1u64 << log_n..2u64 << log_n)
+            .map(FieldElement::random_nonzero_seeded)
+            .collect();
+        group.bench_function(format!("schoolbook 2^{log_n}"), |bench| {
+            bench.iter(|| poly_mul_coeffs(black_box(&a), black_box(&b)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, field_benches, poly_mul_benches);
+criterion_main!(benches);
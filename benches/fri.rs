@@ -0,0 +1,36 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use stark101rs::domain::{subgroup, Roots};
+use stark101rs::field::FieldElement;
+use stark101rs::fri::{fold_inverses, fold_with_inverses};
+use std::hint::black_box;
+
+fn fold_benches(c: &mut Criterion) {
+    // STARK-101 evaluation domain size
+    let n = 1 << 13;
+    let domain = subgroup(Roots::new(n).generator(), n);
+    let evals: Vec<_> = (0..n as u64)
+        .map(FieldElement::random_nonzero_seeded)
+        .collect();
+    let beta = FieldElement::new(42);
+    let inverses = fold_inverses(&domain);
+
+    c.bench_function("fri fold_with_inverses 2^13", |bench| {
+        bench.iter(|| fold_with_inverses(black_box(&evals), black_box(&inverses), beta))
+    });
+    c.bench_function("fri fold per-element division 2^13", |bench| {
+        bench.iter(|| {
+            let evals = black_box(&evals);
+            let half = evals.len() / 2;
+            let two = FieldElement::new(2);
+            (0..half)
+                .map(|i| {
+                    let (f_x, f_neg_x) = (evals[i], evals[i + half]);
+                    (f_x + f_neg_x) / two + beta * (f_x - f_neg_x) / (two * domain[i])
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+}
+
+criterion_group!(benches, fold_benches);
+criterion_main!(benches);
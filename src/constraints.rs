@@ -0,0 +1,82 @@
+use crate::field::FieldElement;
+use alloc::vec::Vec;
+
+/// Computes 1 / (x - x0) for every x in the domain with a single batch
+/// inversion. The entry is `None` where x == x0.
+pub fn boundary_denom_inverse(
+    domain: &[FieldElement],
+    x0: FieldElement,
+) -> Vec<Option<FieldElement>> {
+    let denoms: Vec<_> = domain
+        .iter()
+        .filter(|&&x| x != x0)
+        .map(|&x| x - x0)
+        .collect();
+    let mut inverses = FieldElement::batch_inverse(&denoms).into_iter();
+
+    domain
+        .iter()
+        .map(|&x| if x == x0 { None } else { inverses.next() })
+        .collect()
+}
+
+/// Pointwise quotient num[i] / den[i] with a single batch inversion.
+/// A zero denominator means the domain was set up wrong, so this panics.
+pub fn quotient_evals(num: &[FieldElement], den: &[FieldElement]) -> Vec<FieldElement> {
+    assert_eq!(num.len(), den.len());
+    assert!(
+        den.iter().all(|&d| d != FieldElement::zero()),
+        "zero denominator in quotient evaluations"
+    );
+    FieldElement::batch_inverse(den)
+        .into_iter()
+        .zip(num)
+        .map(|(d_inv, &n)| n * d_inv)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boundary_denom_inverse() {
+        let domain: Vec<_> = (1..9).map(FieldElement::new).collect();
+
+        // x0 outside the domain
+        let x0 = FieldElement::new(100);
+        let inverses = boundary_denom_inverse(&domain, x0);
+        for (&x, inv) in domain.iter().zip(&inverses) {
+            assert_eq!(inv.unwrap(), (x - x0).inverse());
+        }
+
+        // x0 inside the domain
+        let x0 = FieldElement::new(3);
+        let inverses = boundary_denom_inverse(&domain, x0);
+        for (&x, inv) in domain.iter().zip(&inverses) {
+            if x == x0 {
+                assert!(inv.is_none());
+            } else {
+                assert_eq!(inv.unwrap(), (x - x0).inverse());
+            }
+        }
+    }
+
+    #[test]
+    fn test_quotient_evals() {
+        let num: Vec<_> = (10..20).map(FieldElement::new).collect();
+        let den: Vec<_> = (1..11).map(FieldElement::new).collect();
+        let quotient = quotient_evals(&num, &den);
+        for i in 0..num.len() {
+            assert_eq!(quotient[i], num[i] / den[i]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_quotient_evals_zero_denominator() {
+        let num = [FieldElement::one(), FieldElement::one()];
+        let den = [FieldElement::one(), FieldElement::zero()];
+        quotient_evals(&num, &den);
+    }
+}
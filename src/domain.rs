@@ -0,0 +1,343 @@
+use crate::field::FieldElement;
+use alloc::vec::Vec;
+
+/// Size of the evaluation domain for a polynomial of the given degree:
+/// the trace size (degree + 1) rounded up to a power of two, times blowup.
+/// Panics if the size does not fit in a u32.
+pub fn fit_domain(degree: usize, blowup: u32) -> u32 {
+    degree
+        .checked_add(1)
+        .and_then(usize::checked_next_power_of_two)
+        .and_then(|size| u32::try_from(size).ok())
+        .and_then(|size| size.checked_mul(blowup))
+        .expect("domain size does not fit in a u32")
+}
+
+/// `count` shifts g, g^2, ..., g^count for the field generator g, each giving
+/// a different coset of the subgroup H of the given order, none of them H.
+/// F*/H is cyclic of order m = (p-1)/order, generated by gH, so g^i and g^j
+/// share a coset iff i = j mod m. This needs count < m, and `order` must be
+/// a power of two below `max_ntt_size` (for the full 2^30 subgroup, m = 3).
+pub fn coset_shifts(count: usize, order: u32) -> Vec<FieldElement> {
+    assert!(
+        order.is_power_of_two() && order < FieldElement::max_ntt_size(),
+        "subgroup order {order} must be a power of two below 2^30"
+    );
+    let num_cosets = (FieldElement::get_prime() - 1) / order;
+    assert!(
+        count < num_cosets as usize,
+        "only {} cosets besides the subgroup of order {order}",
+        num_cosets - 1
+    );
+    let g = FieldElement::new(FieldElement::one().get_generator());
+    core::iter::successors(Some(g), |&x| Some(x * g))
+        .take(count)
+        .collect()
+}
+
+/// Lazily yields g^0, g^1, ..., g^{order-1}.
+pub fn subgroup_iter(generator: FieldElement, order: u32) -> impl Iterator<Item = FieldElement> {
+    core::iter::successors(Some(FieldElement::one()), move |&x| Some(x * generator))
+        .take(order as usize)
+}
+
+/// The subgroup g^0, ..., g^{order-1} as a vector.
+pub fn subgroup(generator: FieldElement, order: u32) -> Vec<FieldElement> {
+    (0..order).map(|i| generator.pow(i)).collect()
+}
+
+/// Evaluates the derivative of the vanishing polynomial X^n - 1 at x,
+/// i.e. n * x^{n-1}. Used for barycentric weights over a subgroup.
+pub fn vanishing_derivative_eval(x: FieldElement, n: u32) -> FieldElement {
+    assert!(n >= 1);
+    FieldElement::new(n) * x.pow(n - 1)
+}
+
+/// In a multiplicative subgroup of even order, domain[i + n/2] == -domain[i].
+/// Returns the index of that -x partner.
+pub fn partner_index(i: u32, layer_size: u32) -> u32 {
+    (i + layer_size / 2) % layer_size
+}
+
+/// Returns the -x partner of domain[i].
+pub fn partner_point(domain: &[FieldElement], i: u32) -> FieldElement {
+    domain[partner_index(i, domain.len() as u32) as usize]
+}
+
+/// Panics if the evaluation domain shares a point with the trace domain.
+/// The trace domain is a subgroup of order n, so x lies in it iff x^n == 1;
+/// this avoids comparing every pair of points.
+pub fn assert_disjoint_coset(trace_domain: &[FieldElement], eval_domain: &[FieldElement]) {
+    let n = trace_domain.len() as u32;
+    for (i, x) in eval_domain.iter().enumerate() {
+        assert!(
+            x.pow(n) != FieldElement::one(),
+            "eval_domain[{i}] lies in the trace domain"
+        );
+    }
+}
+
+/// 1 / domain[i] for every point, with a single batch inversion.
+/// Panics if the domain contains zero, which no coset does.
+pub fn inverse_domain(domain: &[FieldElement]) -> Vec<FieldElement> {
+    FieldElement::batch_inverse(domain)
+}
+
+/// The coset `coset_shift * <subgroup_gen>` computed once, so that repeated
+/// lookups of `domain[index]` don't recompute `g^index`.
+pub struct DomainMap {
+    points: Vec<FieldElement>,
+}
+
+impl DomainMap {
+    pub fn new(coset_shift: FieldElement, subgroup_gen: FieldElement, size: u32) -> Self {
+        let mut points = Vec::with_capacity(size as usize);
+        let mut x = coset_shift;
+        for _ in 0..size {
+            points.push(x);
+            x *= subgroup_gen;
+        }
+        Self { points }
+    }
+
+    pub fn point(&self, index: u32) -> FieldElement {
+        self.points[index as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+/// Cached powers w^0, ..., w^{n-1} of a primitive nth root of unity w,
+/// so they are computed once and shared instead of per use.
+pub struct Roots {
+    powers: Vec<FieldElement>,
+}
+
+impl Roots {
+    /// `n` must divide p - 1.
+    pub fn new(n: u32) -> Self {
+        let p = FieldElement::get_prime();
+        assert!(
+            n != 0 && (p - 1) % n == 0,
+            "no {n}th roots of unity in the field"
+        );
+        let g = FieldElement::new(FieldElement::one().get_generator());
+        Self {
+            powers: subgroup(g.pow((p - 1) / n), n),
+        }
+    }
+
+    /// w^i, with i taken mod n.
+    pub fn get(&self, i: usize) -> FieldElement {
+        self.powers[i % self.powers.len()]
+    }
+
+    /// The primitive root w itself.
+    pub fn generator(&self) -> FieldElement {
+        self.get(1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.powers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.powers.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vanishing_derivative_eval() {
+        let n = 8;
+        let g = Roots::new(n).generator();
+
+        // on the subgroup x^n = 1, so Z'(x) = n / x
+        for x in subgroup(g, n) {
+            assert_eq!(vanishing_derivative_eval(x, n), FieldElement::new(n) / x);
+        }
+
+        // 3 * 2^2
+        let x = FieldElement::new(2);
+        assert_eq!(vanishing_derivative_eval(x, 3), FieldElement::new(12));
+    }
+
+    #[test]
+    fn test_partner_index() {
+        let n = 16;
+        let domain = subgroup(Roots::new(n).generator(), n);
+
+        for i in 0..n {
+            let neg_x = -domain[i as usize];
+            assert_eq!(domain[partner_index(i, n) as usize], neg_x);
+            assert_eq!(partner_point(&domain, i), neg_x);
+        }
+        assert_eq!(partner_index(3, 8), 7);
+        assert_eq!(partner_index(5, 8), 1);
+    }
+
+    #[test]
+    fn test_domain_map() {
+        let n = 32;
+        let shift = FieldElement::new(5);
+        let g = Roots::new(n).generator();
+        let map = DomainMap::new(shift, g, n);
+
+        assert_eq!(map.len(), n as usize);
+        for i in 0..n {
+            assert_eq!(map.point(i), shift * g.pow(i));
+        }
+    }
+
+    #[test]
+    fn test_assert_disjoint_coset() {
+        let trace_domain = subgroup(Roots::new(8).generator(), 8);
+        let shift = FieldElement::new(5);
+        let eval_domain: Vec<_> = subgroup(Roots::new(64).generator(), 64)
+            .into_iter()
+            .map(|x| shift * x)
+            .collect();
+
+        assert_disjoint_coset(&trace_domain, &eval_domain);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_disjoint_coset_unshifted() {
+        let trace_domain = subgroup(Roots::new(8).generator(), 8);
+        let eval_domain = subgroup(Roots::new(64).generator(), 64);
+
+        assert_disjoint_coset(&trace_domain, &eval_domain);
+    }
+
+    #[test]
+    fn test_subgroup_iter() {
+        let n = 64;
+        let g = Roots::new(n).generator();
+        let lazy: Vec<_> = subgroup_iter(g, n).collect();
+        assert_eq!(lazy, subgroup(g, n));
+        assert_eq!(subgroup_iter(g, 0).count(), 0);
+    }
+
+    #[test]
+    fn test_fit_domain() {
+        // STARK-101: a degree 1022 trace polynomial with blowup 8
+        assert_eq!(fit_domain(1022, 8), 8192);
+        assert_eq!(fit_domain(1023, 8), 8192);
+        assert_eq!(fit_domain(1024, 8), 16384);
+        assert_eq!(fit_domain(0, 4), 4);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    #[should_panic(expected = "domain size does not fit in a u32")]
+    fn test_fit_domain_too_large() {
+        fit_domain(1 << 32, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "domain size does not fit in a u32")]
+    fn test_fit_domain_usize_overflow() {
+        // degree + 1 is just above the top power of two of a usize
+        fit_domain(usize::MAX / 2 + 1, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "domain size does not fit in a u32")]
+    fn test_fit_domain_degree_max() {
+        fit_domain(usize::MAX, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "domain size does not fit in a u32")]
+    fn test_fit_domain_blowup_overflow() {
+        fit_domain(1 << 30, 8);
+    }
+
+    #[test]
+    fn test_inverse_domain() {
+        let domain: Vec<_> = subgroup(Roots::new(32).generator(), 32)
+            .into_iter()
+            .map(|x| FieldElement::new(5) * x)
+            .collect();
+        for (x, x_inv) in domain.iter().zip(inverse_domain(&domain)) {
+            assert_eq!(*x * x_inv, FieldElement::one());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_inverse_domain_with_zero() {
+        inverse_domain(&[FieldElement::one(), FieldElement::zero()]);
+    }
+
+    #[test]
+    fn test_coset_shifts() {
+        let order = 8;
+        let shifts = coset_shifts(16, order);
+        assert_eq!(shifts.len(), 16);
+        assert_eq!(shifts[0], FieldElement::new(5));
+        assert_eq!(shifts[1], FieldElement::new(25));
+
+        // x and y share a coset of H iff (x / y)^order == 1
+        for (i, &x) in shifts.iter().enumerate() {
+            assert_ne!(x.pow(order), FieldElement::one());
+            for &y in &shifts[..i] {
+                assert_ne!((x / y).pow(order), FieldElement::one());
+            }
+        }
+
+        // the largest subgroup allowed leaves 5 cosets besides itself
+        let shifts = coset_shifts(5, 1 << 29);
+        for (i, &x) in shifts.iter().enumerate() {
+            assert_ne!(x.pow(1 << 29), FieldElement::one());
+            for &y in &shifts[..i] {
+                assert_ne!((x / y).pow(1 << 29), FieldElement::one());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "only 5 cosets")]
+    fn test_coset_shifts_shared_coset() {
+        // g^6 lies in the subgroup of order 2^29, so g and g^7 share a coset
+        let g = FieldElement::new(5);
+        assert_eq!(g.pow(6).pow(1 << 29), FieldElement::one());
+        coset_shifts(7, 1 << 29);
+    }
+
+    #[test]
+    #[should_panic(expected = "must be a power of two below 2^30")]
+    fn test_coset_shifts_max_subgroup() {
+        // g^3 = 125 generates the subgroup of order 2^30 itself
+        assert_eq!(FieldElement::new(125).pow(1 << 30), FieldElement::one());
+        coset_shifts(2, FieldElement::max_ntt_size());
+    }
+
+    #[test]
+    fn test_roots() {
+        let roots = Roots::new(8);
+        let w = roots.generator();
+        assert!(w.is_order(8));
+        assert_eq!(roots.len(), 8);
+        for i in 0..16 {
+            assert_eq!(roots.get(i), w.pow(i as u32));
+        }
+        assert_eq!(Roots::new(1).get(0), FieldElement::one());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_roots_zero() {
+        Roots::new(0);
+    }
+}
@@ -1,6 +1,35 @@
-use modulo::Mod;
-use rand::{self, Rng};
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign};
+use alloc::{vec, vec::Vec};
+use core::iter::{Product, Sum};
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+#[cfg(feature = "std")]
+use rand::{self, rngs::StdRng, Rng, SeedableRng};
+
+/// Reduces `x` into [0, p). The prime is fixed, so plain `%` is enough.
+fn reduce(x: u64, p: u64) -> u32 {
+    (x % p) as u32
+}
+
+#[cfg(feature = "ff")]
+mod ff_impl;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldError {
+    /// The encoded value is not below p.
+    NonCanonical(u32),
+}
+
+impl core::fmt::Display for FieldError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FieldError::NonCanonical(val) => {
+                write!(f, "{val} is not a canonical field element")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FieldError {}
 
 #[derive(Debug, Clone)]
 pub struct FieldElement {
@@ -16,7 +45,7 @@ impl FieldElement {
         let p = 3 * 2u32.pow(30) + 1;
         let generator = 5;
         Self {
-            val: val.modulo(p),
+            val: reduce(val as u64, p as u64),
             p,
             generator,
         }
@@ -34,6 +63,57 @@ impl FieldElement {
         3 * 2u32.pow(30) + 1
     }
 
+    /// Exponent of 2 in p - 1; p - 1 = 3 * 2^30, so this is 30.
+    pub fn two_adicity() -> u32 {
+        (Self::get_prime() - 1).trailing_zeros()
+    }
+
+    /// Largest power-of-two subgroup order, and so the largest NTT size.
+    pub fn max_ntt_size() -> u32 {
+        1 << Self::two_adicity()
+    }
+
+    /// Returns the canonical representative in [0, p).
+    pub fn value(&self) -> u32 {
+        self.val
+    }
+
+    /// Additive inverse: p - val, or zero for zero.
+    pub fn neg(&self) -> Self {
+        if self.val == 0 {
+            Self::zero()
+        } else {
+            Self::new(self.p - self.val)
+        }
+    }
+
+    /// Whether `val` is a reduced representative, i.e. below p. Every
+    /// constructor reduces, so this is always true; untrusted encodings
+    /// should be checked with `from_bytes_checked` instead.
+    pub fn is_canonical(&self) -> bool {
+        self.val < self.p
+    }
+
+    /// Little-endian encoding of the canonical representative.
+    pub fn to_bytes(&self) -> [u8; 4] {
+        self.val.to_le_bytes()
+    }
+
+    /// Inverse of `to_bytes`; values >= p are reduced.
+    pub fn from_bytes(bytes: [u8; 4]) -> Self {
+        Self::new(u32::from_le_bytes(bytes))
+    }
+
+    /// Like `from_bytes`, but rejects values >= p instead of reducing them.
+    /// Use this for untrusted input.
+    pub fn from_bytes_checked(bytes: [u8; 4]) -> Result<Self, FieldError> {
+        let val = u32::from_le_bytes(bytes);
+        if val >= Self::get_prime() {
+            return Err(FieldError::NonCanonical(val));
+        }
+        Ok(Self::new(val))
+    }
+
     /// use Fermat's little theorem
     /// a^p = a (mod p)
     /// a^{p-2} * a = 1 (mod p)
@@ -45,6 +125,52 @@ impl FieldElement {
         self.pow(exp)
     }
 
+    /// Inverse via a fixed addition chain for p - 2 = 2^31 + 2^30 - 1.
+    /// Only valid for p = 3 * 2^30 + 1; agrees with `inverse` but needs
+    /// 30 squarings and 8 multiplications.
+    pub fn inverse_fast(&self) -> Self {
+        if self.val == 0 {
+            panic!("Cannot compute inverse of zero");
+        }
+        // x^{2^k} * tail
+        let sqr_mul = |x: Self, k: u32, tail: Self| {
+            let mut r = x;
+            for _ in 0..k {
+                r = r * r;
+            }
+            r * tail
+        };
+
+        let x1 = *self;
+        let x2 = sqr_mul(x1, 1, x1); // x^{2^2 - 1}
+        let x3 = sqr_mul(x2, 1, x1); // x^{2^3 - 1}
+        let x6 = sqr_mul(x3, 3, x3); // x^{2^6 - 1}
+        let x12 = sqr_mul(x6, 6, x6); // x^{2^12 - 1}
+        let x15 = sqr_mul(x12, 3, x3); // x^{2^15 - 1}
+        let x30 = sqr_mul(x15, 15, x15); // x^{2^30 - 1}
+        let x_2_30 = x30 * x1; // x^{2^30}
+        sqr_mul(x_2_30, 1, x30) // x^{2^31 + 2^30 - 1}
+    }
+
+    /// Montgomery's trick: inverts all elements with a single `inverse` call.
+    /// Panics if any element is zero.
+    pub fn batch_inverse(elements: &[FieldElement]) -> Vec<FieldElement> {
+        let mut prefix = Vec::with_capacity(elements.len());
+        let mut acc = Self::one();
+        for &x in elements {
+            prefix.push(acc);
+            acc *= x;
+        }
+
+        let mut acc_inv = acc.inverse();
+        let mut result = vec![Self::zero(); elements.len()];
+        for i in (0..elements.len()).rev() {
+            result[i] = acc_inv * prefix[i];
+            acc_inv *= elements[i];
+        }
+        result
+    }
+
     pub fn pow(&self, exp: u32) -> Self {
         let mut base = self.val;
         let mut result = 1u32;
@@ -52,9 +178,9 @@ impl FieldElement {
 
         while exponent > 0 {
             if exponent & 1 == 1 {
-                result = (result as u64 * base as u64 % self.p as u64) as u32;
+                result = reduce(result as u64 * base as u64, self.p as u64);
             }
-            base = (base as u64 * base as u64 % self.p as u64) as u32;
+            base = reduce(base as u64 * base as u64, self.p as u64);
             exponent >>= 1;
         }
         Self::new(result)
@@ -76,19 +202,50 @@ impl FieldElement {
         }
 
         for i in 2..n {
-            if n % i == 0 {
-                if self.pow(i) == FieldElement::one() {
-                    return false;
-                }
+            if n % i == 0 && self.pow(i) == FieldElement::one() {
+                return false;
             }
         }
-        return true;
+        true
     }
 
+    /// Sum of all elements, zero for an empty iterator.
+    pub fn sum(iter: impl IntoIterator<Item = FieldElement>) -> Self {
+        iter.into_iter().sum()
+    }
+
+    /// Product of all elements, one for an empty iterator.
+    pub fn product(iter: impl IntoIterator<Item = FieldElement>) -> Self {
+        iter.into_iter().product()
+    }
+
+    #[cfg(feature = "std")]
     pub fn random_element() -> Self {
         let mut rng = rand::thread_rng();
         Self::new(rng.gen_range(0..Self::get_prime()))
     }
+
+    /// Draws random elements until a nonzero one comes up.
+    #[cfg(feature = "std")]
+    pub fn random_nonzero() -> Self {
+        Self::random_nonzero_from(&mut rand::thread_rng())
+    }
+
+    /// Same as `random_nonzero`, but reproducible from `seed`.
+    #[cfg(feature = "std")]
+    pub fn random_nonzero_seeded(seed: u64) -> Self {
+        Self::random_nonzero_from(&mut StdRng::seed_from_u64(seed))
+    }
+
+    #[cfg(feature = "std")]
+    fn random_nonzero_from(rng: &mut impl Rng) -> Self {
+        loop {
+            let val = rng.gen_range(0..Self::get_prime());
+            if val != 0 {
+                return Self::new(val);
+            }
+        }
+    }
 }
 
 impl Copy for FieldElement {}
@@ -105,7 +262,7 @@ impl Add for FieldElement {
     type Output = Self;
 
     fn add(self, other: Self) -> Self {
-        Self::new((self.val + other.val).modulo(self.p))
+        Self::new(reduce(self.val as u64 + other.val as u64, self.p as u64))
     }
 }
 
@@ -113,11 +270,43 @@ impl Sub for FieldElement {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self {
-        if self.val < other.val {
-            Self::new((self.p + self.val - other.val).modulo(self.p))
-        } else {
-            Self::new((self.val - other.val).modulo(self.p))
-        }
+        // p + val can exceed u32::MAX, so stay in u64 until reduced
+        Self::new(reduce(
+            self.p as u64 + self.val as u64 - other.val as u64,
+            self.p as u64,
+        ))
+    }
+}
+
+impl Sum for FieldElement {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a FieldElement> for FieldElement {
+    fn sum<I: Iterator<Item = &'a FieldElement>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl Product for FieldElement {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a FieldElement> for FieldElement {
+    fn product<I: Iterator<Item = &'a FieldElement>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+impl Neg for FieldElement {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        FieldElement::neg(&self)
     }
 }
 
@@ -125,14 +314,14 @@ impl Mul for FieldElement {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self {
-        let result = (self.val as u64 * other.val as u64) % self.p as u64;
-        Self::new(result as u32)
+        Self::new(reduce(self.val as u64 * other.val as u64, self.p as u64))
     }
 }
 
 impl Div for FieldElement {
     type Output = Self;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, other: Self) -> Self {
         let other_inv = other.inverse();
         self * other_inv
@@ -141,28 +330,27 @@ impl Div for FieldElement {
 
 impl AddAssign for FieldElement {
     fn add_assign(&mut self, other: Self) {
-        self.val = (self.val + other.val).modulo(self.p);
+        self.val = reduce(self.val as u64 + other.val as u64, self.p as u64);
     }
 }
 
 impl SubAssign for FieldElement {
     fn sub_assign(&mut self, other: Self) {
-        if self.val < other.val {
-            self.val = (self.p + self.val - other.val).modulo(self.p);
-        } else {
-            self.val = (self.val - other.val).modulo(self.p);
-        }
+        self.val = reduce(
+            self.p as u64 + self.val as u64 - other.val as u64,
+            self.p as u64,
+        );
     }
 }
 
 impl MulAssign for FieldElement {
     fn mul_assign(&mut self, other: Self) {
-        let result = (self.val as u64 * other.val as u64).modulo(self.p as u64);
-        self.val = result as u32;
+        self.val = reduce(self.val as u64 * other.val as u64, self.p as u64);
     }
 }
 
 impl DivAssign for FieldElement {
+    #[allow(clippy::suspicious_op_assign_impl)]
     fn div_assign(&mut self, other: Self) {
         let other_inv = other.inverse();
         *self *= other_inv;
@@ -172,16 +360,17 @@ impl DivAssign for FieldElement {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use modulo::Mod;
 
     #[test]
     fn test_basic_operations() {
         let a = FieldElement::new(10);
         let b = FieldElement::new(5);
 
-        assert_eq!(FieldElement::new(15).val, (a.clone() + b.clone()).val);
-        assert_eq!(FieldElement::new(5).val, (a.clone() - b.clone()).val);
-        assert_eq!(FieldElement::new(50).val, (a.clone() * b.clone()).val);
-        assert_eq!(FieldElement::new(2).val, (a.clone() / b.clone()).val);
+        assert_eq!(FieldElement::new(15).val, (a + b).val);
+        assert_eq!(FieldElement::new(5).val, (a - b).val);
+        assert_eq!(FieldElement::new(50).val, (a * b).val);
+        assert_eq!(FieldElement::new(2).val, (a / b).val);
     }
 
     #[test]
@@ -190,11 +379,11 @@ mod tests {
         let zero = FieldElement::zero();
         let one = FieldElement::one();
 
-        assert_eq!(a.val, (a.clone() + zero.clone()).val);
-        assert_eq!(a.val, (a.clone() - zero.clone()).val);
-        assert_eq!(zero.val, (a.clone() * zero.clone()).val);
-        assert_eq!(a.val, (a.clone() * one.clone()).val);
-        assert_eq!(a.val, (a.clone() / one.clone()).val);
+        assert_eq!(a.val, (a + zero).val);
+        assert_eq!(a.val, (a - zero).val);
+        assert_eq!(zero.val, (a * zero).val);
+        assert_eq!(a.val, (a * one).val);
+        assert_eq!(a.val, (a / one).val);
     }
 
     #[test]
@@ -203,10 +392,10 @@ mod tests {
         let b = FieldElement::new(u32::MAX - 1);
 
         // These operations should not panic due to overflow
-        let _sum = a.clone() + b.clone();
-        let _diff = a.clone() - b.clone();
-        let _prod = a.clone() * b.clone();
-        let _div = a.clone() / b.clone();
+        let _sum = a + b;
+        let _diff = a - b;
+        let _prod = a * b;
+        let _div = a / b;
     }
 
     #[test]
@@ -215,11 +404,86 @@ mod tests {
         let large = FieldElement::new(10);
 
         // Test subtraction where result would be negative
-        let diff = small.clone() - large.clone();
+        let diff = small - large;
         assert!(diff.val < small.p);
         assert!(diff.val > 0);
     }
 
+    #[test]
+    fn test_bytes_round_trip() {
+        let a = FieldElement::new(123456789);
+        assert_eq!(FieldElement::from_bytes(a.to_bytes()), a);
+        assert_eq!(FieldElement::zero().to_bytes(), [0; 4]);
+
+        let p = FieldElement::get_prime();
+        assert_eq!(
+            FieldElement::from_bytes((p + 1).to_le_bytes()),
+            FieldElement::one()
+        );
+    }
+
+    #[test]
+    fn test_canonical_bytes() {
+        let p = FieldElement::get_prime();
+        for val in [0, 1, 123456789, p - 1] {
+            let a = FieldElement::from_bytes_checked(val.to_le_bytes()).unwrap();
+            assert_eq!(a.value(), val);
+        }
+        for val in [p, p + 1, u32::MAX] {
+            assert_eq!(
+                FieldElement::from_bytes_checked(val.to_le_bytes()),
+                Err(FieldError::NonCanonical(val))
+            );
+        }
+        assert_eq!(
+            FieldError::NonCanonical(p).to_string(),
+            "3221225473 is not a canonical field element"
+        );
+    }
+
+    #[test]
+    fn test_neg() {
+        let a = FieldElement::new(10);
+        assert_eq!(-a + a, FieldElement::zero());
+        assert_eq!(a.neg(), FieldElement::new(FieldElement::get_prime() - 10));
+        assert_eq!(-FieldElement::zero(), FieldElement::zero());
+        assert_eq!(-(-a), a);
+    }
+
+    #[test]
+    fn test_reduce() {
+        let p = FieldElement::get_prime() as u64;
+        let boundaries = [0, 1, p - 1, p, p + 1, 2 * p, u32::MAX as u64, u64::MAX];
+        for x in boundaries {
+            assert_eq!(reduce(x, p) as u64, x.modulo(p));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_reduce_random() {
+        let p = FieldElement::get_prime() as u64;
+        let mut rng = rand::thread_rng();
+        for _ in 0..1000 {
+            let x: u64 = rng.gen();
+            assert_eq!(reduce(x, p) as u64, x.modulo(p));
+        }
+    }
+
+    #[test]
+    fn test_subtraction_overflow() {
+        // p + 2_000_000_000 does not fit in a u32
+        let a = FieldElement::new(2_000_000_000);
+        let b = FieldElement::new(3_000_000_000);
+        let expected = FieldElement::new(FieldElement::get_prime() - 1_000_000_000);
+        assert_eq!(a - b, expected);
+
+        let mut c = a;
+        c -= b;
+        assert_eq!(c, expected);
+        assert_eq!(b - a, FieldElement::new(1_000_000_000));
+    }
+
     #[test]
     fn test_pow() {
         let a = FieldElement::new(5);
@@ -230,6 +494,25 @@ mod tests {
         assert_eq!(a_pow2, a.inverse());
     }
 
+    #[test]
+    fn test_two_adicity() {
+        assert_eq!(FieldElement::two_adicity(), 30);
+        let n = FieldElement::max_ntt_size();
+        assert_eq!(n, 1 << 30);
+
+        // g^{(p-1)/n} has order exactly n
+        let g = FieldElement::new(5).pow((FieldElement::get_prime() - 1) / n);
+        assert_eq!(g.pow(n), FieldElement::one());
+        assert_ne!(g.pow(n / 2), FieldElement::one());
+    }
+
+    #[test]
+    fn test_value() {
+        let a = FieldElement::new(FieldElement::get_prime() + 5);
+        assert_eq!(a.value(), 5);
+        assert_eq!(FieldElement::zero().value(), 0);
+    }
+
     #[test]
     fn test_inverse() {
         let a = FieldElement::new(6);
@@ -239,6 +522,49 @@ mod tests {
         assert_eq!((a * a_inv), FieldElement::one());
     }
 
+    #[test]
+    fn test_batch_inverse() {
+        let elements: Vec<_> = (1..20).map(FieldElement::new).collect();
+        let inverses = FieldElement::batch_inverse(&elements);
+        for (x, x_inv) in elements.iter().zip(inverses) {
+            assert_eq!(x_inv, x.inverse());
+        }
+        assert!(FieldElement::batch_inverse(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_inverse_fast() {
+        assert_eq!(FieldElement::one().inverse_fast(), FieldElement::one());
+        let p_minus_one = FieldElement::new(FieldElement::get_prime() - 1);
+        assert_eq!(p_minus_one.inverse_fast(), p_minus_one.inverse());
+        for val in [2, 5, 123456789] {
+            let a = FieldElement::new(val);
+            assert_eq!(a.inverse_fast(), a.inverse());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_inverse_fast_random() {
+        for _ in 0..100 {
+            let a = FieldElement::random_nonzero();
+            assert_eq!(a.inverse_fast(), a.inverse());
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_batch_inverse_seeded() {
+        // the inputs used by the batch_inverse benchmark
+        let elements: Vec<_> = (0..1u64 << 10)
+            .map(FieldElement::random_nonzero_seeded)
+            .collect();
+        let inverses = FieldElement::batch_inverse(&elements);
+        for (x, x_inv) in elements.iter().zip(inverses) {
+            assert_eq!(x_inv, x.inverse());
+        }
+    }
+
     #[test]
     #[should_panic]
     fn test_zero_inverse() {
@@ -253,12 +579,44 @@ mod tests {
         assert!(a.is_order(FieldElement::get_prime() - 1));
     }
 
+    #[test]
+    fn test_sum_and_product() {
+        let sum = FieldElement::sum([1, 2, 3].map(FieldElement::new));
+        assert_eq!(sum, FieldElement::new(6));
+        let product = FieldElement::product([2, 3, 4].map(FieldElement::new));
+        assert_eq!(product, FieldElement::new(24));
+
+        let elements = [1, 2, 3].map(FieldElement::new);
+        assert_eq!(elements.iter().sum::<FieldElement>(), FieldElement::new(6));
+        assert_eq!(
+            elements.into_iter().product::<FieldElement>(),
+            FieldElement::new(6)
+        );
+
+        assert_eq!(FieldElement::sum(vec![]), FieldElement::zero());
+        assert_eq!(FieldElement::product(vec![]), FieldElement::one());
+    }
+
     // TODO: how to test randomness?
     #[test]
+    #[cfg(feature = "std")]
     fn test_random_element() {
         for _ in 0..100 {
             let a = FieldElement::random_element();
             assert!(a.val < FieldElement::get_prime());
         }
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_random_nonzero() {
+        for _ in 0..100 {
+            assert_ne!(FieldElement::random_nonzero(), FieldElement::zero());
+        }
+        for seed in 0..100 {
+            let a = FieldElement::random_nonzero_seeded(seed);
+            assert_ne!(a, FieldElement::zero());
+            assert_eq!(a, FieldElement::random_nonzero_seeded(seed));
+        }
+    }
 }
@@ -0,0 +1,187 @@
+//! `ff::PrimeField` for `FieldElement`, behind the `ff` feature.
+
+use super::{reduce, FieldElement};
+use core::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
+use ff::{Field, PrimeField};
+use rand_core::RngCore;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, CtOption};
+
+const P: u32 = 3 * 2u32.pow(30) + 1;
+
+impl FieldElement {
+    /// `val` must already be below p.
+    const fn from_reduced(val: u32) -> Self {
+        Self {
+            val,
+            p: P,
+            generator: 5,
+        }
+    }
+}
+
+impl Default for FieldElement {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl From<u64> for FieldElement {
+    fn from(val: u64) -> Self {
+        Self::from_reduced(reduce(val, P as u64))
+    }
+}
+
+impl ConditionallySelectable for FieldElement {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self::from_reduced(u32::conditional_select(&a.val, &b.val, choice))
+    }
+}
+
+impl ConstantTimeEq for FieldElement {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.val.ct_eq(&other.val)
+    }
+}
+
+macro_rules! impl_ref_ops {
+    ($($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident);*) => {
+        $(
+            impl $op<&FieldElement> for FieldElement {
+                type Output = Self;
+
+                fn $fn(self, other: &FieldElement) -> Self {
+                    self.$fn(*other)
+                }
+            }
+
+            impl $op_assign<&FieldElement> for FieldElement {
+                fn $fn_assign(&mut self, other: &FieldElement) {
+                    self.$fn_assign(*other);
+                }
+            }
+        )*
+    };
+}
+
+impl_ref_ops!(
+    Add, add, AddAssign, add_assign;
+    Sub, sub, SubAssign, sub_assign;
+    Mul, mul, MulAssign, mul_assign
+);
+
+impl Field for FieldElement {
+    const ZERO: Self = Self::from_reduced(0);
+    const ONE: Self = Self::from_reduced(1);
+
+    fn random(mut rng: impl RngCore) -> Self {
+        loop {
+            let val = rng.next_u32();
+            if val < P {
+                return Self::from_reduced(val);
+            }
+        }
+    }
+
+    fn square(&self) -> Self {
+        *self * *self
+    }
+
+    fn double(&self) -> Self {
+        *self + *self
+    }
+
+    fn invert(&self) -> CtOption<Self> {
+        let is_nonzero = !self.ct_eq(&Self::ZERO);
+        // inverse_fast panics on zero, so invert a stand-in and discard it
+        let x = Self::conditional_select(&Self::ONE, self, is_nonzero);
+        CtOption::new(x.inverse_fast(), is_nonzero)
+    }
+
+    fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+        ff::helpers::sqrt_ratio_generic(num, div)
+    }
+
+    fn sqrt(&self) -> CtOption<Self> {
+        // p - 1 = 2^S * t with t = 3, so (t - 1) / 2 = 1
+        ff::helpers::sqrt_tonelli_shanks(self, [1u64])
+    }
+}
+
+impl PrimeField for FieldElement {
+    /// Little-endian, as produced by `to_bytes`.
+    type Repr = [u8; 4];
+
+    fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+        let val = u32::from_le_bytes(repr);
+        CtOption::new(Self::from_reduced(val % P), Choice::from((val < P) as u8))
+    }
+
+    fn to_repr(&self) -> Self::Repr {
+        self.to_bytes()
+    }
+
+    fn is_odd(&self) -> Choice {
+        Choice::from((self.val & 1) as u8)
+    }
+
+    const MODULUS: &'static str = "0xc0000001";
+    const NUM_BITS: u32 = 32;
+    const CAPACITY: u32 = 31;
+    /// (p + 1) / 2
+    const TWO_INV: Self = Self::from_reduced(1610612737);
+    const MULTIPLICATIVE_GENERATOR: Self = Self::from_reduced(5);
+    const S: u32 = 30;
+    /// 5^3, of order 2^30
+    const ROOT_OF_UNITY: Self = Self::from_reduced(125);
+    const ROOT_OF_UNITY_INV: Self = Self::from_reduced(2267742733);
+    /// 5^(2^30)
+    const DELTA: Self = Self::from_reduced(1610563584);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repr_round_trip() {
+        for val in [0, 1, 5, 123456789, P - 1] {
+            let a = FieldElement::new(val);
+            let b = FieldElement::from_repr(a.to_repr()).unwrap();
+            assert_eq!(a, b);
+        }
+        assert!(bool::from(
+            FieldElement::from_repr(P.to_le_bytes()).is_none()
+        ));
+    }
+
+    #[test]
+    fn test_modulus() {
+        let modulus = u32::from_str_radix(&FieldElement::MODULUS[2..], 16).unwrap();
+        assert_eq!(modulus, FieldElement::get_prime());
+    }
+
+    #[test]
+    fn test_constants() {
+        let two = FieldElement::new(2);
+        assert_eq!(FieldElement::TWO_INV * two, FieldElement::ONE);
+
+        let root = FieldElement::ROOT_OF_UNITY;
+        assert_eq!(root.pow(1 << 30), FieldElement::ONE);
+        assert_ne!(root.pow(1 << 29), FieldElement::ONE);
+        assert_eq!(root * FieldElement::ROOT_OF_UNITY_INV, FieldElement::ONE);
+
+        let g = FieldElement::MULTIPLICATIVE_GENERATOR;
+        assert_eq!(g.pow(1 << 30), FieldElement::DELTA);
+    }
+
+    #[test]
+    fn test_invert_and_sqrt() {
+        assert!(bool::from(FieldElement::ZERO.invert().is_none()));
+        let a = FieldElement::new(12345);
+        assert_eq!(a.invert().unwrap(), a.inverse());
+
+        let sq = a.square();
+        let root = sq.sqrt().unwrap();
+        assert!(root == a || root == -a);
+    }
+}
@@ -0,0 +1,215 @@
+use crate::field::FieldElement;
+use alloc::vec::Vec;
+
+/// Checks a single FRI folding step at a queried point.
+/// Writing f(X) = g(X^2) + X * h(X^2), the next layer is
+/// g(X^2) + beta * h(X^2), where
+///   g(x^2) = (f(x) + f(-x)) / 2
+///   h(x^2) = (f(x) - f(-x)) / (2x)
+pub fn check_fold(
+    layer_i_x: FieldElement,
+    layer_i_neg_x: FieldElement,
+    x: FieldElement,
+    beta_i: FieldElement,
+    layer_next: FieldElement,
+) -> bool {
+    let two = FieldElement::new(2);
+    let g = (layer_i_x + layer_i_neg_x) / two;
+    let h = (layer_i_x - layer_i_neg_x) / (two * x);
+    g + beta_i * h == layer_next
+}
+
+/// After the last fold, the value reached at a queried index must equal the
+/// constant the prover sent for the final layer.
+pub fn verify_final_constant(folded_value: FieldElement, claimed_constant: FieldElement) -> bool {
+    folded_value == claimed_constant
+}
+
+/// Precomputes 1 / (2 * domain[i]) for the first half of a layer's domain,
+/// to be reused by `fold_with_inverses`.
+pub fn fold_inverses(domain: &[FieldElement]) -> Vec<FieldElement> {
+    let two = FieldElement::new(2);
+    let doubled: Vec<_> = domain[..domain.len() / 2]
+        .iter()
+        .map(|&x| two * x)
+        .collect();
+    FieldElement::batch_inverse(&doubled)
+}
+
+/// Folds a layer's evaluations over a subgroup (or coset) of size n into the
+/// next layer of size n/2, where domain[i + n/2] == -domain[i]:
+///   next[i] = (f(x) + f(-x)) / 2 + beta * (f(x) - f(-x)) / (2x)
+/// `domain_inverses[i]` must hold 1 / (2 * domain[i]), see `fold_inverses`.
+pub fn fold_with_inverses(
+    evals: &[FieldElement],
+    domain_inverses: &[FieldElement],
+    beta: FieldElement,
+) -> Vec<FieldElement> {
+    let half = evals.len() / 2;
+    assert_eq!(domain_inverses.len(), half);
+    let two_inv = FieldElement::new(2).inverse();
+
+    (0..half)
+        .map(|i| {
+            let (f_x, f_neg_x) = (evals[i], evals[i + half]);
+            (f_x + f_neg_x) * two_inv + beta * (f_x - f_neg_x) * domain_inverses[i]
+        })
+        .collect()
+}
+
+/// Number of folds needed to shrink a domain of `initial_domain_size`
+/// down to `final_size`, i.e. log2(initial / final).
+/// Both sizes must be powers of two with initial >= final.
+pub fn num_layers(initial_domain_size: u32, final_size: u32) -> usize {
+    assert!(initial_domain_size.is_power_of_two() && final_size.is_power_of_two());
+    assert!(initial_domain_size >= final_size);
+    (initial_domain_size / final_size).trailing_zeros() as usize
+}
+
+/// Recovers the degree of the polynomial behind `evals`, given over the
+/// subgroup generated by `subgroup_gen`, via a naive O(n^2) inverse DFT:
+///   c_k = 1/n * sum_i evals[i] * g^{-ik}
+/// The degree is the index of the highest nonzero coefficient (0 for the
+/// zero polynomial). Meant as a test oracle, not for proving.
+/// Panics if `evals` is empty.
+pub fn degree_from_evals(evals: &[FieldElement], subgroup_gen: FieldElement) -> usize {
+    assert!(!evals.is_empty(), "no evaluations to recover a degree from");
+    let n = evals.len();
+    let n_inv = FieldElement::new(n as u32).inverse();
+    let g_inv = subgroup_gen.inverse();
+
+    (0..n)
+        .rev()
+        .find(|&k| {
+            let step = g_inv.pow(k as u32);
+            let mut x = FieldElement::one();
+            let mut c_k = FieldElement::zero();
+            for &e in evals {
+                c_k += e * x;
+                x *= step;
+            }
+            c_k * n_inv != FieldElement::zero()
+        })
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{subgroup, Roots};
+
+    fn eval(coeffs: &[u32], x: FieldElement) -> FieldElement {
+        coeffs.iter().rev().fold(FieldElement::zero(), |acc, &c| {
+            acc * x + FieldElement::new(c)
+        })
+    }
+
+    #[test]
+    fn test_check_fold() {
+        // f = 1 + 2X + 3X^2 + 4X^3, so g = 1 + 3Y and h = 2 + 4Y
+        let f = [1, 2, 3, 4];
+        let beta = FieldElement::new(7);
+        let x = FieldElement::new(11);
+        let neg_x = -x;
+        let x_sq = x * x;
+        let next = eval(&[1, 3], x_sq) + beta * eval(&[2, 4], x_sq);
+
+        let f_x = eval(&f, x);
+        let f_neg_x = eval(&f, neg_x);
+        assert!(check_fold(f_x, f_neg_x, x, beta, next));
+
+        // corrupted next layer value
+        assert!(!check_fold(
+            f_x,
+            f_neg_x,
+            x,
+            beta,
+            next + FieldElement::one()
+        ));
+        // inconsistent beta
+        assert!(!check_fold(
+            f_x,
+            f_neg_x,
+            x,
+            beta + FieldElement::one(),
+            next
+        ));
+        // swapped x and -x values
+        assert!(!check_fold(f_neg_x, f_x, x, beta, next));
+    }
+
+    #[test]
+    fn test_fold_with_inverses() {
+        let n = 8;
+        let domain = subgroup(Roots::new(n).generator(), n);
+        let f = [3, 1, 4, 1, 5, 9, 2, 6];
+        let evals: Vec<_> = domain.iter().map(|&x| eval(&f, x)).collect();
+        let beta = FieldElement::new(42);
+
+        let folded = fold_with_inverses(&evals, &fold_inverses(&domain), beta);
+        assert_eq!(folded.len(), 4);
+        for (i, &next) in folded.iter().enumerate() {
+            assert!(check_fold(evals[i], evals[i + 4], domain[i], beta, next));
+        }
+    }
+
+    #[test]
+    fn test_verify_final_constant() {
+        // f = 5 + 3X folds to the constant 5 + beta * 3 in one step
+        let domain = subgroup(Roots::new(2).generator(), 2);
+        let evals: Vec<_> = domain.iter().map(|&x| eval(&[5, 3], x)).collect();
+        let beta = FieldElement::new(10);
+
+        let folded = fold_with_inverses(&evals, &fold_inverses(&domain), beta);
+        let constant = FieldElement::new(35);
+        assert!(verify_final_constant(folded[0], constant));
+        assert!(!verify_final_constant(
+            folded[0],
+            constant + FieldElement::one()
+        ));
+    }
+
+    #[test]
+    fn test_degree_from_evals() {
+        let n = 16;
+        let g = Roots::new(n).generator();
+        let domain = subgroup(g, n);
+
+        let f = [7, 0, 3, 0, 0, 2];
+        let evals: Vec<_> = domain.iter().map(|&x| eval(&f, x)).collect();
+        assert_eq!(degree_from_evals(&evals, g), 5);
+
+        let constant = vec![FieldElement::new(4); n as usize];
+        assert_eq!(degree_from_evals(&constant, g), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "no evaluations")]
+    fn test_degree_from_evals_empty() {
+        degree_from_evals(&[], FieldElement::one());
+    }
+
+    #[test]
+    fn test_num_layers() {
+        assert_eq!(num_layers(16, 2), 3);
+        assert_eq!(num_layers(8192, 1), 13);
+        assert_eq!(num_layers(4, 4), 0);
+
+        // count the folds actually performed from 16 down to 2
+        let mut domain = subgroup(Roots::new(16).generator(), 16);
+        let mut evals: Vec<_> = domain.iter().map(|&x| eval(&[1, 2, 3], x)).collect();
+        let mut folds = 0;
+        while evals.len() > 2 {
+            evals = fold_with_inverses(&evals, &fold_inverses(&domain), FieldElement::new(3));
+            domain = domain[..domain.len() / 2].iter().map(|&x| x * x).collect();
+            folds += 1;
+        }
+        assert_eq!(folds, num_layers(16, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_num_layers_not_power_of_two() {
+        num_layers(12, 2);
+    }
+}
@@ -1 +1,10 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod constraints;
+pub mod domain;
 pub mod field;
+pub mod fri;
+pub mod stark;
+pub mod utils;
@@ -0,0 +1,42 @@
+use crate::field::FieldElement;
+
+/// Checks the boundary quotient relation q(x) = (f(x) - y0) / (x - x0) at a
+/// single decommitted point, in the multiplied-out form
+/// f(x) - y0 == q(x) * (x - x0) so no inversion is needed.
+/// At x == x0 the quotient value is unconstrained and this reduces to
+/// f(x0) == y0.
+pub fn check_boundary(
+    f_x: FieldElement,
+    x: FieldElement,
+    x0: FieldElement,
+    y0: FieldElement,
+    q_x: FieldElement,
+) -> bool {
+    if x == x0 {
+        return f_x == y0;
+    }
+    f_x - y0 == q_x * (x - x0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_boundary() {
+        let x0 = FieldElement::one();
+        let y0 = FieldElement::new(3);
+        let x = FieldElement::new(9);
+        let f_x = FieldElement::new(100);
+        let q_x = (f_x - y0) / (x - x0);
+
+        assert!(check_boundary(f_x, x, x0, y0, q_x));
+        assert!(!check_boundary(f_x, x, x0, y0, q_x + FieldElement::one()));
+        assert!(!check_boundary(f_x + FieldElement::one(), x, x0, y0, q_x));
+        assert!(!check_boundary(f_x, x, x0, FieldElement::new(4), q_x));
+
+        // at x == x0 only f(x0) == y0 can be checked
+        assert!(check_boundary(y0, x0, x0, y0, FieldElement::new(12345)));
+        assert!(!check_boundary(f_x, x0, x0, y0, q_x));
+    }
+}
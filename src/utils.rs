@@ -0,0 +1,111 @@
+use crate::field::FieldElement;
+use alloc::{vec, vec::Vec};
+
+/// Smallest power of two >= n (1 for n = 0).
+/// Panics if that power of two does not fit in a usize.
+pub fn next_pow2(n: usize) -> usize {
+    n.checked_next_power_of_two()
+        .expect("next power of two does not fit in a usize")
+}
+
+/// Reorders `v` so index i moves to the bit-reversal of i. Applying it twice
+/// is the identity. Domains built in `domain` are in natural order.
+/// `v.len()` must be a power of two.
+pub fn bit_reverse_permute(v: &mut [FieldElement]) {
+    let n = v.len();
+    assert!(n.is_power_of_two());
+    let bits = n.trailing_zeros();
+    if bits == 0 {
+        return;
+    }
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            v.swap(i, j);
+        }
+    }
+}
+
+/// Adds two coefficient vectors (lowest degree first).
+/// The result has the length of the longer input; trailing zeros are kept.
+pub fn poly_add_coeffs(a: &[FieldElement], b: &[FieldElement]) -> Vec<FieldElement> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = long.to_vec();
+    for (r, &c) in result.iter_mut().zip(short) {
+        *r += c;
+    }
+    result
+}
+
+/// Schoolbook multiplication of two coefficient vectors (lowest degree first).
+/// Returns an empty vector if either input is empty.
+pub fn poly_mul_coeffs(a: &[FieldElement], b: &[FieldElement]) -> Vec<FieldElement> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut result = vec![FieldElement::zero(); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            result[i + j] += x * y;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coeffs(values: &[u32]) -> Vec<FieldElement> {
+        values.iter().map(|&v| FieldElement::new(v)).collect()
+    }
+
+    #[test]
+    fn test_poly_add_coeffs() {
+        let a = coeffs(&[1, 2]);
+        let b = coeffs(&[3, 4, 5]);
+        assert_eq!(poly_add_coeffs(&a, &b), coeffs(&[4, 6, 5]));
+        assert_eq!(poly_add_coeffs(&b, &a), coeffs(&[4, 6, 5]));
+        assert_eq!(poly_add_coeffs(&a, &[]), a);
+    }
+
+    #[test]
+    fn test_poly_mul_coeffs() {
+        let a = coeffs(&[1, 2]);
+        let b = coeffs(&[3, 4]);
+        assert_eq!(poly_mul_coeffs(&a, &b), coeffs(&[3, 10, 8]));
+        assert_eq!(poly_mul_coeffs(&a, &coeffs(&[1])), a);
+        assert!(poly_mul_coeffs(&a, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_next_pow2() {
+        assert_eq!(next_pow2(0), 1);
+        assert_eq!(next_pow2(1), 1);
+        assert_eq!(next_pow2(8), 8);
+        assert_eq!(next_pow2(9), 16);
+        assert_eq!(next_pow2(1024), 1024);
+        assert_eq!(next_pow2(1025), 2048);
+        assert_eq!(next_pow2(usize::MAX / 2 + 1), usize::MAX / 2 + 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in a usize")]
+    fn test_next_pow2_overflow() {
+        next_pow2(usize::MAX / 2 + 2);
+    }
+
+    #[test]
+    fn test_bit_reverse_permute() {
+        let original = coeffs(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        let mut v = original.clone();
+        bit_reverse_permute(&mut v);
+        assert_eq!(v, coeffs(&[0, 4, 2, 6, 1, 5, 3, 7]));
+        bit_reverse_permute(&mut v);
+        assert_eq!(v, original);
+
+        let mut single = coeffs(&[9]);
+        bit_reverse_permute(&mut single);
+        assert_eq!(single, coeffs(&[9]));
+    }
+}